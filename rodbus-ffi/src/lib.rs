@@ -15,6 +15,16 @@ use tokio::runtime;
 pub mod asynchronous;
 // synchronous API
 pub mod synchronous;
+// connection state change notifications
+pub mod listener;
+// configurable reconnect/backoff strategy
+pub mod reconnect;
+// tunable runtime construction
+pub mod runtime_config;
+// pluggable log/trace sink
+pub mod logging;
+// callback-driven server
+pub mod server;
 
 /// Status returned during synchronous and asynchronous API calls
 #[repr(u8)]
@@ -72,6 +82,8 @@ impl Result {
 
 impl std::convert::From<&ErrorKind> for Result {
     fn from(err: &ErrorKind) -> Self {
+        logging::log_lazy(logging::LogLevel::Debug, || format!("request error: {}", err));
+
         match err {
             ErrorKind::Bug(_) => Result::status(Status::InternalError),
             ErrorKind::NoConnection => Result::status(Status::NoConnection),
@@ -96,8 +108,8 @@ impl<T> std::convert::From<std::result::Result<T, rodbus::error::Error>> for Res
     }
 }
 
-struct ContextStorage {
-    context: *mut c_void,
+pub(crate) struct ContextStorage {
+    pub(crate) context: *mut c_void,
 }
 
 #[repr(C)]
@@ -188,36 +200,42 @@ pub extern "C" fn build_session(
     }
 }
 
+/// parse a C string address into a #SocketAddr, returning None if it is not valid UTF-8
+/// or not a valid "ip:port" pair (an IP address literal; host names are not resolved)
+pub(crate) unsafe fn parse_socket_addr(address: *const std::os::raw::c_char) -> Option<SocketAddr> {
+    match CStr::from_ptr(address).to_str() {
+        Err(err) => {
+            logging::log_lazy(logging::LogLevel::Error, || {
+                format!("address is not valid UTF8: {}", err)
+            });
+            None
+        }
+        Ok(s) => match SocketAddr::from_str(s) {
+            Err(err) => {
+                logging::log_lazy(logging::LogLevel::Error, || {
+                    format!("unable to parse '{}' as a SocketAddr: {}", s, err)
+                });
+                None
+            }
+            Ok(addr) => Some(addr),
+        },
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn create_tcp_client(
     runtime: *mut tokio::runtime::Runtime,
     address: *const std::os::raw::c_char,
     max_queued_requests: usize,
 ) -> *mut rodbus::client::channel::Channel {
-    let rt = runtime.as_mut().unwrap();
-
-    // if we can't turn the c-string into SocketAddr, return null
-    let addr = {
-        match CStr::from_ptr(address).to_str() {
-            // TODO - consider logging?
-            Err(_) => return null_mut(),
-            Ok(s) => match SocketAddr::from_str(s) {
-                // TODO - consider logging?
-                Err(_) => return null_mut(),
-                Ok(addr) => addr,
-            },
-        }
-    };
-
-    let (handle, task) = rodbus::client::channel::Channel::create_handle_and_task(
-        addr,
+    // preserves the exact reconnect timing prior clients got, rather than the possibly-different
+    // fixed bounds of reconnect::ReconnectStrategy::default()
+    reconnect::create_tcp_client_with_strategy(
+        runtime,
+        address,
         max_queued_requests,
         rodbus::client::channel::strategy::default(),
-    );
-
-    rt.spawn(task);
-
-    Box::into_raw(Box::new(handle))
+    )
 }
 
 #[no_mangle]
@@ -0,0 +1,83 @@
+use crate::ContextStorage;
+// This module's only external surface is Channel::set_state_listener(Box<dyn FnMut(ClientState) + Send>)
+// and the ClientState enum it takes. Neither is present in this FFI crate's own source, so they
+// must come from the `rodbus` dependency itself; this tree has no Cargo.toml/vendored `rodbus`
+// to build against, so that assumption is unverified here.
+use rodbus::client::channel::{Channel, ClientState};
+use std::os::raw::c_void;
+
+/// @brief Connection state of a #Channel as reported to a registered connection listener
+#[repr(u8)]
+pub enum ConnectionState {
+    /// The channel is attempting to establish a connection to the server
+    Connecting,
+    /// The channel has an open connection to the server
+    Connected,
+    /// The connection to the server was lost and the channel is waiting to reconnect
+    Disconnected,
+    /// The channel task has been shut down and will never reconnect
+    Shutdown,
+}
+
+impl std::convert::From<ClientState> for ConnectionState {
+    fn from(state: ClientState) -> Self {
+        match state {
+            ClientState::Connecting => ConnectionState::Connecting,
+            ClientState::Connected => ConnectionState::Connected,
+            ClientState::Disconnected => ConnectionState::Disconnected,
+            ClientState::Shutdown => ConnectionState::Shutdown,
+        }
+    }
+}
+
+/// @brief Callback invoked whenever the connection state of a #Channel changes
+///
+/// @param state     the new #ConnectionState of the channel
+/// @param context   opaque value supplied to set_connection_listener(), passed back unchanged
+pub type ConnectionStateCallback =
+    unsafe extern "C" fn(state: ConnectionState, context: *mut c_void);
+
+struct ConnectionListener {
+    callback: ConnectionStateCallback,
+    context: ContextStorage,
+}
+
+unsafe impl Send for ConnectionListener {}
+
+impl ConnectionListener {
+    fn on_state_change(&self, state: ClientState) {
+        unsafe { (self.callback)(state.into(), self.context.context) }
+    }
+}
+
+/// @brief Register a callback to be invoked whenever the connection state of the channel changes
+///
+/// The callback is invoked from the Tokio runtime task that drives the channel's reconnect loop,
+/// so it must not block. Only one listener may be registered per channel; registering a new one
+/// replaces the previous listener.
+///
+/// @param channel   channel on which to observe connection state changes
+/// @param callback  callback invoked on every state transition (Connecting / Connected / Disconnected / Shutdown)
+/// @param context   opaque context value that will be passed back to the callback unchanged
+#[no_mangle]
+pub unsafe extern "C" fn set_connection_listener(
+    channel: *mut Channel,
+    callback: ConnectionStateCallback,
+    context: *mut c_void,
+) {
+    let channel = match channel.as_mut() {
+        Some(x) => x,
+        None => return,
+    };
+
+    let listener = ConnectionListener {
+        callback,
+        context: ContextStorage { context },
+    };
+
+    // Channel::set_state_listener takes Box<dyn FnMut(ClientState) + Send>; the closure only
+    // needs Fn, but is annotated as FnMut here to match that bound exactly
+    let listener: Box<dyn FnMut(ClientState) + Send> =
+        Box::new(move |state| listener.on_state_change(state));
+    channel.set_state_listener(listener);
+}
@@ -0,0 +1,191 @@
+use log::{Level, LevelFilter, Metadata, Record};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Once, RwLock};
+
+/// @brief Severity of a message delivered to the log handler registered via set_log_handler()
+#[repr(u8)]
+#[derive(Copy, Clone, PartialEq, PartialOrd)]
+pub enum LogLevel {
+    /// Unrecoverable condition, e.g. a malformed configuration
+    Error,
+    /// Recoverable but noteworthy condition, e.g. a dropped connection
+    Warn,
+    /// High-level lifecycle event, e.g. a successful reconnect
+    Info,
+    /// Detailed diagnostic information, e.g. a framing error
+    Debug,
+    /// Byte-level tracing of requests and responses
+    Trace,
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => LogLevel::Error,
+            Level::Warn => LogLevel::Warn,
+            Level::Info => LogLevel::Info,
+            Level::Debug => LogLevel::Debug,
+            Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+/// @brief Callback invoked for every log message at or above the level given to set_log_handler()
+///
+/// @param level     severity of the message
+/// @param message   NUL-terminated UTF-8 message; only valid for the duration of the callback
+/// @param context   opaque value supplied to set_log_handler(), passed back unchanged
+pub type LogCallback = unsafe extern "C" fn(level: LogLevel, message: *const c_char, context: *mut c_void);
+
+struct LogHandler {
+    callback: LogCallback,
+    context: usize,
+}
+
+unsafe impl Send for LogHandler {}
+unsafe impl Sync for LogHandler {}
+
+static LOG_HANDLER: RwLock<Option<LogHandler>> = RwLock::new(None);
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Error as u8);
+
+// mirrors LOG_HANDLER so is_enabled() can short-circuit without taking the RwLock;
+// 0 means "no handler registered"
+static HANDLER_CALLBACK: AtomicUsize = AtomicUsize::new(0);
+
+static LOGGER_INIT: Once = Once::new();
+
+struct FfiLogger;
+
+impl log::Log for FfiLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        is_enabled(metadata.level().into())
+    }
+
+    fn log(&self, record: &Record) {
+        log_lazy(record.level().into(), || format!("{}", record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+/// @brief Register a global handler that receives all log/trace messages produced by the library
+///
+/// This installs the handler as the sink for the crate's `log` facade, so reconnect attempts,
+/// framing errors, timeouts, and address-parse failures raised anywhere in the library - not
+/// just in this FFI layer - flow to the host application's logging system. If another `log`
+/// logger was already installed in the process, it stays in place and only messages raised by
+/// this FFI layer reach the handler.
+///
+/// The handler must be safe to call concurrently from any runtime worker thread. Registering a
+/// new handler replaces the previous one; passing `None` disables logging entirely.
+///
+/// @param callback   handler invoked for every message at or above `level`, or NULL to disable logging
+/// @param context    opaque context value passed back to the callback unchanged
+/// @param level      minimum severity that will be delivered to the callback
+#[no_mangle]
+pub unsafe extern "C" fn set_log_handler(callback: Option<LogCallback>, context: *mut c_void, level: LogLevel) {
+    LOGGER_INIT.call_once(|| {
+        let _ = log::set_boxed_logger(Box::new(FfiLogger));
+    });
+    log::set_max_level(level.into());
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+
+    match callback {
+        Some(callback) => {
+            HANDLER_CALLBACK.store(callback as usize, Ordering::Relaxed);
+            *LOG_HANDLER.write().unwrap() = Some(LogHandler {
+                callback,
+                context: context as usize,
+            });
+        }
+        None => clear_log_handler(),
+    }
+}
+
+/// @brief Remove any previously registered log handler
+#[no_mangle]
+pub extern "C" fn clear_log_handler() {
+    HANDLER_CALLBACK.store(0, Ordering::Relaxed);
+    *LOG_HANDLER.write().unwrap() = None;
+}
+
+/// true if `level` is at or above `max_level`, i.e. severe enough to be delivered
+fn level_allowed(level: LogLevel, max_level: u8) -> bool {
+    (level as u8) <= max_level
+}
+
+pub(crate) fn is_enabled(level: LogLevel) -> bool {
+    HANDLER_CALLBACK.load(Ordering::Relaxed) != 0
+        && level_allowed(level, LOG_LEVEL.load(Ordering::Relaxed))
+}
+
+/// dispatch a pre-formatted message to the registered handler, if any
+fn log(level: LogLevel, message: &str) {
+    let guard = match LOG_HANDLER.read() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+
+    if let Some(handler) = guard.as_ref() {
+        if let Ok(c_message) = CString::new(message) {
+            unsafe { (handler.callback)(level, c_message.as_ptr(), handler.context as *mut c_void) }
+        }
+    }
+}
+
+/// dispatch a message to the registered handler, building it with `f` only if `level` is enabled
+///
+/// Callers should always go through this instead of `log()` directly: it keeps the common case
+/// of "no handler registered" free of the `format!()` allocation.
+pub(crate) fn log_lazy(level: LogLevel, f: impl FnOnce() -> String) {
+    if !is_enabled(level) {
+        return;
+    }
+
+    log(level, &f());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_are_allowed_at_every_max_level() {
+        for max in [
+            LogLevel::Error,
+            LogLevel::Warn,
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::Trace,
+        ] {
+            assert!(level_allowed(LogLevel::Error, max as u8));
+        }
+    }
+
+    #[test]
+    fn trace_is_only_allowed_at_max_level_trace() {
+        assert!(!level_allowed(LogLevel::Trace, LogLevel::Debug as u8));
+        assert!(level_allowed(LogLevel::Trace, LogLevel::Trace as u8));
+    }
+
+    #[test]
+    fn level_allowed_is_a_at_or_above_check() {
+        assert!(level_allowed(LogLevel::Warn, LogLevel::Info as u8));
+        assert!(level_allowed(LogLevel::Info, LogLevel::Info as u8));
+        assert!(!level_allowed(LogLevel::Debug, LogLevel::Info as u8));
+    }
+}
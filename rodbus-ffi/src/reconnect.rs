@@ -0,0 +1,139 @@
+use rodbus::client::channel::strategy::ReconnectStrategy as LibReconnectStrategy;
+use std::ptr::null_mut;
+use std::time::Duration;
+
+/// @brief Configuration of the bounded exponential backoff used to reconnect a #Channel
+///
+/// The delay between reconnect attempts starts at `min_delay_ms`, doubles after every
+/// failed attempt, is capped at `max_delay_ms`, and resets back to `min_delay_ms` as soon
+/// as a connection succeeds.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ReconnectStrategy {
+    /// delay before the first reconnect attempt, and the delay used immediately after a successful connection
+    pub min_delay_ms: u64,
+    /// upper bound on the delay between reconnect attempts
+    pub max_delay_ms: u64,
+}
+
+impl Default for ReconnectStrategy {
+    /// one second minimum delay, one minute maximum delay
+    fn default() -> Self {
+        Self {
+            min_delay_ms: 1000,
+            max_delay_ms: 60_000,
+        }
+    }
+}
+
+struct ExponentialBackoff {
+    min_delay: Duration,
+    max_delay: Duration,
+    current_delay: Duration,
+}
+
+impl ExponentialBackoff {
+    fn new(strategy: ReconnectStrategy) -> Self {
+        let min_delay = Duration::from_millis(strategy.min_delay_ms);
+        Self {
+            min_delay,
+            max_delay: Duration::from_millis(strategy.max_delay_ms),
+            current_delay: min_delay,
+        }
+    }
+}
+
+impl LibReconnectStrategy for ExponentialBackoff {
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.current_delay;
+        self.current_delay = (self.current_delay * 2).min(self.max_delay);
+        crate::logging::log_lazy(crate::logging::LogLevel::Info, || {
+            format!("reconnecting in {:?}", delay)
+        });
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.current_delay = self.min_delay;
+    }
+}
+
+/// shared by create_tcp_client() and create_tcp_client_ex(): the only difference between them
+/// is which `ReconnectStrategy` implementation gets boxed up
+pub(crate) unsafe fn create_tcp_client_with_strategy(
+    runtime: *mut tokio::runtime::Runtime,
+    address: *const std::os::raw::c_char,
+    max_queued_requests: usize,
+    strategy: Box<dyn LibReconnectStrategy + Send>,
+) -> *mut rodbus::client::channel::Channel {
+    let rt = runtime.as_mut().unwrap();
+
+    let addr = match crate::parse_socket_addr(address) {
+        Some(addr) => addr,
+        None => return null_mut(),
+    };
+
+    let (handle, task) =
+        rodbus::client::channel::Channel::create_handle_and_task(addr, max_queued_requests, strategy);
+
+    rt.spawn(task);
+
+    Box::into_raw(Box::new(handle))
+}
+
+/// @brief create a TCP client #Channel using a custom reconnect/backoff strategy
+///
+/// This is identical to create_tcp_client() except that the delay between reconnect
+/// attempts is controlled by the supplied #ReconnectStrategy instead of the default.
+///
+/// @param runtime               pointer to the #Runtime on which the channel task will be spawned
+/// @param address               "ip:port" of the remote Modbus TCP server (host names are not resolved)
+/// @param max_queued_requests   maximum number of requests that may be queued on the channel
+/// @param strategy              reconnect/backoff configuration
+/// @return                      pointer to the created #Channel, or NULL if the address is invalid
+#[no_mangle]
+pub unsafe extern "C" fn create_tcp_client_ex(
+    runtime: *mut tokio::runtime::Runtime,
+    address: *const std::os::raw::c_char,
+    max_queued_requests: usize,
+    strategy: ReconnectStrategy,
+) -> *mut rodbus::client::channel::Channel {
+    create_tcp_client_with_strategy(
+        runtime,
+        address,
+        max_queued_requests,
+        Box::new(ExponentialBackoff::new(strategy)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_delay_until_capped() {
+        let mut backoff = ExponentialBackoff::new(ReconnectStrategy {
+            min_delay_ms: 100,
+            max_delay_ms: 400,
+        });
+
+        assert_eq!(backoff.next_delay(), Duration::from_millis(100));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(200));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(400));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn reset_returns_to_min_delay() {
+        let mut backoff = ExponentialBackoff::new(ReconnectStrategy {
+            min_delay_ms: 50,
+            max_delay_ms: 200,
+        });
+
+        backoff.next_delay();
+        backoff.next_delay();
+        backoff.reset();
+
+        assert_eq!(backoff.next_delay(), Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,115 @@
+use std::os::raw::c_char;
+use std::ptr::null_mut;
+use tokio::runtime;
+
+/// @brief Tuning parameters used by create_runtime() to build a Tokio runtime
+///
+/// A zero value for any numeric field falls back to the Tokio default for that field.
+/// A NULL `thread_name` falls back to Tokio's default thread naming.
+#[repr(C)]
+pub struct RuntimeConfig {
+    /// number of worker threads used to poll futures; 0 uses the Tokio default (one per CPU core)
+    pub worker_threads: usize,
+    /// name given to every runtime worker thread, e.g. for diagnostics; may be NULL
+    pub thread_name: *const c_char,
+    /// stack size in bytes for each worker thread; 0 uses the Tokio default
+    pub thread_stack_size: usize,
+    /// maximum number of threads the runtime may use in total, including `worker_threads`
+    /// and the blocking pool; 0 uses the Tokio default. When non-zero, must be >= the number
+    /// of worker threads (`worker_threads`, or the CPU count when `worker_threads` is 0),
+    /// otherwise create_runtime() returns NULL
+    pub max_threads: usize,
+}
+
+/// the number of core threads tokio::runtime::Builder will actually use: `worker_threads`,
+/// or the CPU count when it is left at 0 (the same default tokio applies internally)
+fn effective_core_threads(worker_threads: usize) -> usize {
+    if worker_threads != 0 {
+        worker_threads
+    } else {
+        num_cpus::get()
+    }
+}
+
+/// true if `Builder::build()` would panic with these core/max thread counts
+fn exceeds_max_threads(core_threads: usize, max_threads: usize) -> bool {
+    max_threads != 0 && core_threads > max_threads
+}
+
+/// @brief Create a Tokio runtime configured according to the supplied #RuntimeConfig
+///
+/// This is useful on embedded/SCADA gateways where operators need to pin Modbus polling
+/// to a fixed, small thread pool and give the threads recognizable names for diagnostics.
+///
+/// This instance is typically created at the beginning of your program and destroyed
+/// using destroy_runtime() before your program exits.
+///
+/// @param config   tuning parameters for the runtime; see #RuntimeConfig
+/// @return          An instance of the runtime or NULL if it cannot be created for some reason
+#[no_mangle]
+pub unsafe extern "C" fn create_runtime(config: RuntimeConfig) -> *mut runtime::Runtime {
+    // `Builder::build()` panics if core_threads > max_threads, so reject that combination
+    // up front instead of letting it panic, per the NULL-on-failure contract documented above.
+    // worker_threads == 0 still resolves to a core_threads count (the CPU count), so that case
+    // must be checked too, not just an explicit worker_threads.
+    if exceeds_max_threads(effective_core_threads(config.worker_threads), config.max_threads) {
+        return null_mut();
+    }
+
+    let mut builder = runtime::Builder::new();
+    builder.enable_all().threaded_scheduler();
+
+    if config.worker_threads != 0 {
+        builder.core_threads(config.worker_threads);
+    }
+
+    if config.max_threads != 0 {
+        builder.max_threads(config.max_threads);
+    }
+
+    if config.thread_stack_size != 0 {
+        builder.thread_stack_size(config.thread_stack_size);
+    }
+
+    if !config.thread_name.is_null() {
+        if let Ok(name) = std::ffi::CStr::from_ptr(config.thread_name).to_str() {
+            builder.thread_name(name);
+        }
+    }
+
+    match builder.build() {
+        Ok(r) => Box::into_raw(Box::new(r)),
+        Err(_) => null_mut(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effective_core_threads_uses_explicit_worker_count() {
+        assert_eq!(effective_core_threads(4), 4);
+    }
+
+    #[test]
+    fn effective_core_threads_falls_back_to_cpu_count_when_zero() {
+        assert_eq!(effective_core_threads(0), num_cpus::get());
+    }
+
+    #[test]
+    fn exceeds_max_threads_rejects_core_threads_above_the_cap() {
+        assert!(exceeds_max_threads(8, 2));
+    }
+
+    #[test]
+    fn exceeds_max_threads_accepts_core_threads_at_or_below_the_cap() {
+        assert!(!exceeds_max_threads(2, 2));
+        assert!(!exceeds_max_threads(2, 8));
+    }
+
+    #[test]
+    fn exceeds_max_threads_treats_zero_as_uncapped() {
+        assert!(!exceeds_max_threads(64, 0));
+    }
+}
@@ -0,0 +1,292 @@
+use crate::Result;
+use rodbus::error::details::ExceptionCode;
+// ServerHandlerMap::single() and server::create_tcp_server_task() are assumed entry points on the
+// `rodbus` dependency, not something this FFI crate defines. This tree has no Cargo.toml/vendored
+// `rodbus` to build against, so their exact signatures are unverified here.
+use rodbus::server::handler::{ServerHandler, ServerHandlerMap};
+use rodbus::types::{AddressRange, UnitId, WriteMultiple};
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+use std::sync::{Arc, Mutex};
+
+/// @brief Callback invoked to service a "read coils" or "read discrete inputs" request
+///
+/// The handler must write exactly `range.count` boolean values into `out` before returning.
+///
+/// @param unit_id   Modbus unit identifier the server was bound to
+/// @param range     starting address and count of the points being read
+/// @param out       buffer of `range.count` booleans that the handler must fill
+/// @param context   opaque context value supplied to create_tcp_server(), passed back unchanged
+/// @return          a #Result with status #Status_Ok on success, or #Status_Exception and an exception code on failure
+pub type ReadBitsCallback =
+    unsafe extern "C" fn(unit_id: UnitId, range: AddressRange, out: *mut bool, context: *mut c_void) -> Result;
+
+/// @brief Callback invoked to service a "read holding registers" or "read input registers" request
+///
+/// The handler must write exactly `range.count` register values into `out` before returning.
+pub type ReadRegistersCallback =
+    unsafe extern "C" fn(unit_id: UnitId, range: AddressRange, out: *mut u16, context: *mut c_void) -> Result;
+
+/// @brief Callback invoked to service a "write single coil" request
+pub type WriteSingleCoilCallback =
+    unsafe extern "C" fn(unit_id: UnitId, index: u16, value: bool, context: *mut c_void) -> Result;
+
+/// @brief Callback invoked to service a "write single register" request
+pub type WriteSingleRegisterCallback =
+    unsafe extern "C" fn(unit_id: UnitId, index: u16, value: u16, context: *mut c_void) -> Result;
+
+/// @brief Callback invoked to service a "write multiple coils" request
+pub type WriteMultipleCoilsCallback = unsafe extern "C" fn(
+    unit_id: UnitId,
+    start: u16,
+    values: *const bool,
+    count: u16,
+    context: *mut c_void,
+) -> Result;
+
+/// @brief Callback invoked to service a "write multiple registers" request
+pub type WriteMultipleRegistersCallback = unsafe extern "C" fn(
+    unit_id: UnitId,
+    start: u16,
+    values: *const u16,
+    count: u16,
+    context: *mut c_void,
+) -> Result;
+
+/// @brief vtable of callbacks used to service incoming requests on a server created with create_tcp_server()
+///
+/// One function pointer is provided per Modbus function code supported by the server. All
+/// callbacks receive the same opaque `context` value, mirroring the `context` already used for
+/// asynchronous request completion elsewhere in this library.
+#[repr(C)]
+pub struct RequestHandler {
+    /// Modbus unit identifier this handler services; requests addressed to any other unit id
+    /// are not dispatched to it (the exact resulting behavior - an exception reply or a dropped
+    /// request - is determined by rodbus's server dispatch, not by this FFI layer)
+    pub unit_id: u8,
+    pub context: *mut c_void,
+    pub read_coils: ReadBitsCallback,
+    pub read_discrete_inputs: ReadBitsCallback,
+    pub read_holding_registers: ReadRegistersCallback,
+    pub read_input_registers: ReadRegistersCallback,
+    pub write_single_coil: WriteSingleCoilCallback,
+    pub write_single_register: WriteSingleRegisterCallback,
+    pub write_multiple_coils: WriteMultipleCoilsCallback,
+    pub write_multiple_registers: WriteMultipleRegistersCallback,
+}
+
+unsafe impl Send for RequestHandler {}
+
+fn to_modbus_result(result: Result) -> std::result::Result<(), ExceptionCode> {
+    match result.status {
+        crate::Status::Ok => Ok(()),
+        crate::Status::Exception => Err(ExceptionCode::from_u8(result.exception)),
+        _ => Err(ExceptionCode::ServerDeviceFailure),
+    }
+}
+
+// Dispatch in rodbus::server routes by unit id via ServerHandlerMap before a ServerHandler's
+// methods are ever called, so (unlike the C vtable) those methods don't take a UnitId. This
+// adapter is registered under a single unit id and remembers it so it can still be threaded
+// through to the C callbacks, which do want it.
+struct FfiServerHandler {
+    vtable: RequestHandler,
+    unit_id: UnitId,
+    coils_scratch: Vec<bool>,
+    registers_scratch: Vec<u16>,
+}
+
+unsafe impl Send for FfiServerHandler {}
+
+impl ServerHandler for FfiServerHandler {
+    fn read_coils(&mut self, range: AddressRange) -> std::result::Result<&[bool], ExceptionCode> {
+        self.coils_scratch.clear();
+        self.coils_scratch.resize(range.count as usize, false);
+        to_modbus_result(unsafe {
+            (self.vtable.read_coils)(self.unit_id, range, self.coils_scratch.as_mut_ptr(), self.vtable.context)
+        })?;
+        Ok(&self.coils_scratch)
+    }
+
+    fn read_discrete_inputs(&mut self, range: AddressRange) -> std::result::Result<&[bool], ExceptionCode> {
+        self.coils_scratch.clear();
+        self.coils_scratch.resize(range.count as usize, false);
+        to_modbus_result(unsafe {
+            (self.vtable.read_discrete_inputs)(
+                self.unit_id,
+                range,
+                self.coils_scratch.as_mut_ptr(),
+                self.vtable.context,
+            )
+        })?;
+        Ok(&self.coils_scratch)
+    }
+
+    fn read_holding_registers(&mut self, range: AddressRange) -> std::result::Result<&[u16], ExceptionCode> {
+        self.registers_scratch.clear();
+        self.registers_scratch.resize(range.count as usize, 0);
+        to_modbus_result(unsafe {
+            (self.vtable.read_holding_registers)(
+                self.unit_id,
+                range,
+                self.registers_scratch.as_mut_ptr(),
+                self.vtable.context,
+            )
+        })?;
+        Ok(&self.registers_scratch)
+    }
+
+    fn read_input_registers(&mut self, range: AddressRange) -> std::result::Result<&[u16], ExceptionCode> {
+        self.registers_scratch.clear();
+        self.registers_scratch.resize(range.count as usize, 0);
+        to_modbus_result(unsafe {
+            (self.vtable.read_input_registers)(
+                self.unit_id,
+                range,
+                self.registers_scratch.as_mut_ptr(),
+                self.vtable.context,
+            )
+        })?;
+        Ok(&self.registers_scratch)
+    }
+
+    fn write_single_coil(&mut self, index: u16, value: bool) -> std::result::Result<(), ExceptionCode> {
+        to_modbus_result(unsafe {
+            (self.vtable.write_single_coil)(self.unit_id, index, value, self.vtable.context)
+        })
+    }
+
+    fn write_single_register(&mut self, index: u16, value: u16) -> std::result::Result<(), ExceptionCode> {
+        to_modbus_result(unsafe {
+            (self.vtable.write_single_register)(self.unit_id, index, value, self.vtable.context)
+        })
+    }
+
+    fn write_multiple_coils(&mut self, values: WriteMultiple<bool>) -> std::result::Result<(), ExceptionCode> {
+        to_modbus_result(unsafe {
+            (self.vtable.write_multiple_coils)(
+                self.unit_id,
+                values.start,
+                values.values.as_ptr(),
+                values.values.len() as u16,
+                self.vtable.context,
+            )
+        })
+    }
+
+    fn write_multiple_registers(&mut self, values: WriteMultiple<u16>) -> std::result::Result<(), ExceptionCode> {
+        to_modbus_result(unsafe {
+            (self.vtable.write_multiple_registers)(
+                self.unit_id,
+                values.start,
+                values.values.as_ptr(),
+                values.values.len() as u16,
+                self.vtable.context,
+            )
+        })
+    }
+}
+
+/// @brief Opaque handle to a running TCP server created with create_tcp_server()
+pub struct ServerHandle {
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+/// @brief Create and start a TCP server that dispatches incoming requests to `handler`
+///
+/// This turns the library into a usable device-emulator/gateway building block for C and C++
+/// programs. `handler` services only the single unit id given by its `unit_id` field; see
+/// #RequestHandler for what happens to requests addressed to any other unit id.
+///
+/// @param runtime        pointer to the #Runtime on which the server's listener task will be spawned
+/// @param address        "ip:port" on which to listen for incoming connections (host names are not resolved)
+/// @param max_sessions   maximum number of concurrent TCP sessions the server will accept
+/// @param handler        vtable of callbacks invoked to service each incoming request
+/// @return                opaque handle to the running server, or NULL if the address is invalid
+#[no_mangle]
+pub unsafe extern "C" fn create_tcp_server(
+    runtime: *mut tokio::runtime::Runtime,
+    address: *const std::os::raw::c_char,
+    max_sessions: usize,
+    handler: RequestHandler,
+) -> *mut ServerHandle {
+    let rt = runtime.as_mut().unwrap();
+
+    let addr = match crate::parse_socket_addr(address) {
+        Some(addr) => addr,
+        None => return null_mut(),
+    };
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+
+    let unit_id = UnitId::new(handler.unit_id);
+    let adapter = FfiServerHandler {
+        vtable: handler,
+        unit_id,
+        coils_scratch: Vec::new(),
+        registers_scratch: Vec::new(),
+    };
+    let handlers = ServerHandlerMap::single(unit_id, Arc::new(Mutex::new(adapter)));
+
+    let task = rodbus::server::create_tcp_server_task(shutdown_rx, addr, max_sessions, handlers);
+
+    rt.spawn(task);
+
+    Box::into_raw(Box::new(ServerHandle {
+        shutdown: Some(shutdown_tx),
+    }))
+}
+
+/// @brief Stop and destroy a previously created TCP server
+///
+/// This operation is typically performed just before program exit. Existing sessions are
+/// dropped and the listening socket is closed.
+#[no_mangle]
+pub unsafe extern "C" fn destroy_tcp_server(server: *mut ServerHandle) {
+    if !server.is_null() {
+        if let Some(sender) = Box::from_raw(server).shutdown.take() {
+            let _ = sender.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Result, Status};
+
+    #[test]
+    fn ok_status_maps_to_ok() {
+        assert!(to_modbus_result(Result {
+            status: Status::Ok,
+            exception: 0,
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn exception_status_maps_to_the_carried_exception_code() {
+        let result = Result {
+            status: Status::Exception,
+            exception: ExceptionCode::IllegalDataAddress.to_u8(),
+        };
+
+        match to_modbus_result(result) {
+            Err(ExceptionCode::IllegalDataAddress) => {}
+            other => panic!("expected IllegalDataAddress, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn other_statuses_map_to_server_device_failure() {
+        let result = Result {
+            status: Status::IOError,
+            exception: 0,
+        };
+
+        match to_modbus_result(result) {
+            Err(ExceptionCode::ServerDeviceFailure) => {}
+            other => panic!("expected ServerDeviceFailure, got {:?}", other.err()),
+        }
+    }
+}